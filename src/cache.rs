@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A remembered hash result for one file, keyed by its absolute path. Kept
+/// valid only while `size` and `mtime` still match the file on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub size: u64,
+    pub mtime: i64,
+    pub partial_hash: Option<u128>,
+    pub full_hash: Option<u128>,
+}
+
+pub type Cache = HashMap<PathBuf, CacheEntry>;
+
+/// Load a hash cache from disk. A missing or unreadable file yields an
+/// empty cache rather than an error, since the cache is purely an
+/// optimization.
+pub fn load(path: &Path) -> Cache {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Write the cache back to disk, dropping entries for files that no longer
+/// exist.
+pub fn save(path: &Path, cache: &Cache) -> std::io::Result<()> {
+    let pruned: Cache = cache
+        .iter()
+        .filter(|(p, _)| p.exists())
+        .map(|(p, e)| (p.clone(), e.clone()))
+        .collect();
+    let contents = serde_json::to_string(&pruned)?;
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ddh_cachetest_{}_{}", name, std::process::id()))
+    }
+
+    fn entry(size: u64) -> CacheEntry {
+        CacheEntry {
+            size,
+            mtime: 0,
+            partial_hash: Some(1),
+            full_hash: Some(2),
+        }
+    }
+
+    #[test]
+    fn save_then_load_round_trips_entries_for_existing_files() {
+        let cache_path = temp_path("roundtrip_cache");
+        let file_path = temp_path("roundtrip_file");
+        fs::write(&file_path, b"exists").unwrap();
+
+        let mut cache = Cache::new();
+        cache.insert(file_path.clone(), entry(6));
+        save(&cache_path, &cache).unwrap();
+
+        let loaded = load(&cache_path);
+        assert_eq!(loaded.get(&file_path).unwrap().size, 6);
+
+        fs::remove_file(&file_path).ok();
+        fs::remove_file(&cache_path).ok();
+    }
+
+    #[test]
+    fn save_prunes_entries_for_files_that_no_longer_exist() {
+        let cache_path = temp_path("prune_cache");
+        let missing_path = temp_path("prune_missing_file");
+
+        let mut cache = Cache::new();
+        cache.insert(missing_path, entry(1));
+        save(&cache_path, &cache).unwrap();
+
+        let loaded = load(&cache_path);
+        assert!(loaded.is_empty());
+
+        fs::remove_file(&cache_path).ok();
+    }
+
+    #[test]
+    fn load_returns_empty_cache_for_missing_file() {
+        let cache_path = temp_path("nonexistent_cache");
+        let loaded = load(&cache_path);
+        assert!(loaded.is_empty());
+    }
+}
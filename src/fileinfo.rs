@@ -0,0 +1,66 @@
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A single unique file (by size, plus partial and full hash unless the
+/// comparison method stops earlier), and every path on disk that matched it.
+/// A `Fileinfo` with one path is a singleton; more than one path means the
+/// files are duplicates of each other as far as the comparison method
+/// checked, which for the size and partial methods is not the full content.
+#[derive(Debug, Clone, Serialize)]
+pub struct Fileinfo {
+    length: u64,
+    partial_hash: Option<u128>,
+    full_hash: Option<u128>,
+    paths: Vec<PathBuf>,
+}
+
+impl Fileinfo {
+    pub fn new(
+        length: u64,
+        partial_hash: Option<u128>,
+        full_hash: Option<u128>,
+        path: PathBuf,
+    ) -> Fileinfo {
+        Fileinfo {
+            length,
+            partial_hash,
+            full_hash,
+            paths: vec![path],
+        }
+    }
+
+    pub fn get_length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn get_partial_hash(&self) -> Option<u128> {
+        self.partial_hash
+    }
+
+    pub fn get_full_hash(&self) -> Option<u128> {
+        self.full_hash
+    }
+
+    pub fn get_paths(&self) -> &Vec<PathBuf> {
+        &self.paths
+    }
+
+    pub fn get_mut_paths(&mut self) -> &mut Vec<PathBuf> {
+        &mut self.paths
+    }
+
+    pub fn add_path(&mut self, path: PathBuf) {
+        self.paths.push(path);
+    }
+
+    /// A representative display name for this group of paths: the file
+    /// name of the first path on record.
+    pub fn get_candidate_name(&self) -> String {
+        self.paths
+            .first()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string()
+    }
+}
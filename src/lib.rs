@@ -0,0 +1,729 @@
+pub mod cache;
+pub mod fileinfo;
+pub mod progress;
+
+use cache::{Cache, CacheEntry};
+use fileinfo::Fileinfo;
+use progress::{Phase, ProgressData};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+/// Number of leading bytes hashed when computing a file's partial hash.
+const PARTIAL_HASH_SIZE: u64 = 4096;
+
+/// Chunk size used by the `Bytes` method's final byte-for-byte comparison.
+const VERIFY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// How thoroughly two files must agree before they're reported as
+/// duplicates of each other, trading speed for certainty.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ComparisonMethod {
+    /// Group purely by file length. Fast, approximate.
+    Size,
+    /// Group by length, then by a hash of the first [`PARTIAL_HASH_SIZE`]
+    /// bytes.
+    Partial,
+    /// Group by length, then partial hash, then a hash of the full
+    /// contents. The default.
+    Full,
+    /// Same as `Full`, plus a final byte-for-byte comparison pass over
+    /// every group that collided on the full hash, splitting apart any
+    /// group whose contents actually differ.
+    Bytes,
+}
+
+impl Default for ComparisonMethod {
+    fn default() -> Self {
+        ComparisonMethod::Full
+    }
+}
+
+/// Tuning knobs for [`deduplicate_dirs`], grouped into one struct so the
+/// function itself doesn't accumulate a parameter per feature.
+#[derive(Default)]
+pub struct ScanOptions<'a> {
+    /// Subtrees to skip entirely.
+    pub ignore_dirs: Vec<&'a str>,
+    /// Files smaller than this are skipped.
+    pub min_size: u64,
+    /// Path to a hash cache file, reused and updated across runs.
+    pub cache_file: Option<&'a Path>,
+    /// How thoroughly to compare files.
+    pub method: ComparisonMethod,
+    /// Only consider files with one of these (lowercase) extensions, when
+    /// non-empty.
+    pub allowed_extensions: Vec<&'a str>,
+    /// Skip files with one of these (lowercase) extensions.
+    pub excluded_extensions: Vec<&'a str>,
+    /// Channel to stream scan progress over, if the caller wants it.
+    pub progress: Option<Sender<ProgressData>>,
+}
+
+/// Discover every file under `search_dirs` (skipping anything rooted under
+/// `options.ignore_dirs` and anything smaller than `options.min_size`),
+/// then group them by content into [`Fileinfo`] records. Files are first
+/// bucketed by length, then, depending on `options.method`, by a hash of
+/// their first [`PARTIAL_HASH_SIZE`] bytes and/or a hash of their full
+/// contents, so that expensive full-file hashing is only ever done on
+/// files that already collided on the cheaper checks.
+/// [`ComparisonMethod::Bytes`] additionally verifies every full-hash match
+/// with a byte-for-byte comparison.
+///
+/// When `options.cache_file` is given, size+mtime-matched hashes are
+/// reused from a prior run instead of being recomputed, and the merged
+/// cache (including any newly computed hashes) is written back to that
+/// path before returning.
+///
+/// Returns the discovered files (singletons and duplicate groups alike)
+/// alongside any paths that could not be read, paired with the `io::Error`
+/// encountered.
+pub fn deduplicate_dirs(
+    search_dirs: Vec<&str>,
+    options: ScanOptions,
+) -> (Vec<Fileinfo>, Vec<(PathBuf, io::Error)>) {
+    let ScanOptions {
+        ignore_dirs,
+        min_size,
+        cache_file,
+        method,
+        allowed_extensions,
+        excluded_extensions,
+        progress,
+    } = options;
+
+    let ignore_dirs: Vec<PathBuf> = ignore_dirs.iter().map(PathBuf::from).collect();
+    let allowed_extensions: Vec<String> = allowed_extensions
+        .iter()
+        .map(|e| e.to_lowercase())
+        .collect();
+    let excluded_extensions: Vec<String> = excluded_extensions
+        .iter()
+        .map(|e| e.to_lowercase())
+        .collect();
+    let reporter = progress.map(ProgressReporter::new);
+    let (candidates, mut errors) = discover_candidates(
+        &search_dirs,
+        &ignore_dirs,
+        min_size,
+        &allowed_extensions,
+        &excluded_extensions,
+        &reporter,
+    );
+
+    let loaded_cache = cache_file.map(cache::load).unwrap_or_default();
+    let cache: Mutex<Cache> = Mutex::new(loaded_cache);
+
+    let mut complete_files: Vec<Fileinfo> = Vec::new();
+    let by_length = group_by(candidates, |c: &Candidate| c.length);
+
+    for (length, same_length) in by_length {
+        if same_length.len() == 1 || method == ComparisonMethod::Size {
+            let mut paths = same_length.into_iter().map(|c| c.path);
+            let first = paths.next().unwrap();
+            let mut info = Fileinfo::new(length, None, None, first);
+            for path in paths {
+                info.add_path(path);
+            }
+            complete_files.push(info);
+            continue;
+        }
+
+        let (partial_hashed, partial_errors): (Vec<_>, Vec<_>) = same_length
+            .into_par_iter()
+            .map(|Candidate { path, length, mtime }| {
+                let result = cached_or_compute(&cache, &path, length, mtime, CacheField::Partial);
+                match result {
+                    Ok((hash, bytes_read)) => {
+                        if let (Some(reporter), Some(bytes_read)) = (&reporter, bytes_read) {
+                            reporter.hashed(bytes_read, Phase::PartialHash);
+                        }
+                        Ok((path, length, mtime, hash))
+                    }
+                    Err(e) => Err((path, e)),
+                }
+            })
+            .partition_map(|r| match r {
+                Ok(v) => rayon::iter::Either::Left(v),
+                Err(v) => rayon::iter::Either::Right(v),
+            });
+        errors.extend(partial_errors);
+
+        let by_partial = group_by(partial_hashed, |(_, _, _, hash)| *hash);
+        for (partial_hash, same_partial) in by_partial {
+            if same_partial.len() == 1 || method == ComparisonMethod::Partial {
+                let mut paths = same_partial.into_iter().map(|(p, _, _, _)| p);
+                let first = paths.next().unwrap();
+                let mut info = Fileinfo::new(length, Some(partial_hash), None, first);
+                for path in paths {
+                    info.add_path(path);
+                }
+                complete_files.push(info);
+                continue;
+            }
+
+            let (full_hashed, full_errors): (Vec<_>, Vec<_>) = same_partial
+                .into_par_iter()
+                .map(|(path, length, mtime, _)| {
+                    let result = cached_or_compute(&cache, &path, length, mtime, CacheField::Full);
+                    match result {
+                        Ok((hash, bytes_read)) => {
+                            if let (Some(reporter), Some(bytes_read)) = (&reporter, bytes_read) {
+                                reporter.hashed(bytes_read, Phase::FullHash);
+                            }
+                            Ok((path, hash))
+                        }
+                        Err(e) => Err((path, e)),
+                    }
+                })
+                .partition_map(|r| match r {
+                    Ok(v) => rayon::iter::Either::Left(v),
+                    Err(v) => rayon::iter::Either::Right(v),
+                });
+            errors.extend(full_errors);
+
+            let by_full = group_by(full_hashed, |(_, hash)| *hash);
+            for (full_hash, same_full) in by_full {
+                let mut paths = same_full.into_iter().map(|(p, _)| p);
+                let first = paths.next().unwrap();
+                let mut info = Fileinfo::new(length, Some(partial_hash), Some(full_hash), first);
+                for path in paths {
+                    info.add_path(path);
+                }
+                complete_files.push(info);
+            }
+        }
+    }
+
+    if let Some(cache_file) = cache_file {
+        let cache = cache.into_inner().unwrap_or_default();
+        if let Err(e) = cache::save(cache_file, &cache) {
+            errors.push((cache_file.to_path_buf(), e));
+        }
+    }
+
+    if method == ComparisonMethod::Bytes {
+        let (verified, verify_errors) = verify_by_bytes(complete_files);
+        errors.extend(verify_errors);
+        complete_files = verified;
+    }
+
+    (complete_files, errors)
+}
+
+/// For every group with more than one path, confirm the files are actually
+/// byte-for-byte identical and split apart any group where they are not
+/// (an astronomically unlikely but real full-hash collision).
+fn verify_by_bytes(
+    groups: Vec<Fileinfo>,
+) -> (Vec<Fileinfo>, Vec<(PathBuf, io::Error)>) {
+    let results: Vec<Result<Vec<Fileinfo>, (PathBuf, io::Error)>> = groups
+        .into_par_iter()
+        .map(|group| {
+            if group.get_paths().len() < 2 {
+                return Ok(vec![group]);
+            }
+
+            let length = group.get_length();
+            let partial_hash = group.get_partial_hash();
+            let full_hash = group.get_full_hash();
+            let mut clusters: Vec<Vec<PathBuf>> = Vec::new();
+            for path in group.get_paths().iter().cloned() {
+                let mut placed = false;
+                for cluster in clusters.iter_mut() {
+                    if files_equal(&cluster[0], &path).map_err(|e| (path.clone(), e))? {
+                        cluster.push(path.clone());
+                        placed = true;
+                        break;
+                    }
+                }
+                if !placed {
+                    clusters.push(vec![path]);
+                }
+            }
+
+            Ok(clusters
+                .into_iter()
+                .map(|mut paths| {
+                    let first = paths.remove(0);
+                    let mut info = Fileinfo::new(length, partial_hash, full_hash, first);
+                    for path in paths {
+                        info.add_path(path);
+                    }
+                    info
+                })
+                .collect())
+        })
+        .collect();
+
+    let mut verified = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(mut infos) => verified.append(&mut infos),
+            Err(e) => errors.push(e),
+        }
+    }
+    (verified, errors)
+}
+
+/// Compare two files' contents in fixed-size chunks, without loading either
+/// file fully into memory.
+fn files_equal(a: &Path, b: &Path) -> io::Result<bool> {
+    let mut a = fs::File::open(a)?;
+    let mut b = fs::File::open(b)?;
+    let mut buf_a = vec![0u8; VERIFY_CHUNK_SIZE];
+    let mut buf_b = vec![0u8; VERIFY_CHUNK_SIZE];
+    loop {
+        let n_a = a.read(&mut buf_a)?;
+        let n_b = b.read(&mut buf_b)?;
+        if n_a != n_b || buf_a[..n_a] != buf_b[..n_b] {
+            return Ok(false);
+        }
+        if n_a == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Which hash a cache lookup/update is for.
+enum CacheField {
+    Partial,
+    Full,
+}
+
+/// Reuse a file's cached hash if its size and mtime still match, otherwise
+/// compute it and record the result back into the cache. Returns the hash
+/// alongside the number of bytes read from disk to produce it — `None` on
+/// a cache hit, since nothing was read; `Some(0)` is a legitimate miss
+/// result for an empty file.
+fn cached_or_compute(
+    cache: &Mutex<Cache>,
+    path: &Path,
+    length: u64,
+    mtime: i64,
+    field: CacheField,
+) -> io::Result<(u128, Option<u64>)> {
+    let key = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    if let Some(entry) = cache.lock().unwrap().get(&key) {
+        if entry.size == length && entry.mtime == mtime {
+            let cached = match field {
+                CacheField::Partial => entry.partial_hash,
+                CacheField::Full => entry.full_hash,
+            };
+            if let Some(hash) = cached {
+                return Ok((hash, None));
+            }
+        }
+    }
+
+    let (hash, bytes_read) = match field {
+        CacheField::Partial => hash_prefix(path, PARTIAL_HASH_SIZE)?,
+        CacheField::Full => hash_file(path)?,
+    };
+
+    let mut cache = cache.lock().unwrap();
+    let entry = cache.entry(key).or_insert(CacheEntry {
+        size: length,
+        mtime,
+        partial_hash: None,
+        full_hash: None,
+    });
+    if entry.size != length || entry.mtime != mtime {
+        entry.size = length;
+        entry.mtime = mtime;
+        entry.partial_hash = None;
+        entry.full_hash = None;
+    }
+    match field {
+        CacheField::Partial => entry.partial_hash = Some(hash),
+        CacheField::Full => entry.full_hash = Some(hash),
+    }
+
+    Ok((hash, Some(bytes_read)))
+}
+
+/// Modification time as nanoseconds since the epoch. Whole-second resolution
+/// isn't enough to invalidate the cache: a file rewritten with the same
+/// length within the same second as a prior scan would otherwise look
+/// unchanged and hand back a stale hash.
+fn mtime_nanos(meta: &fs::Metadata) -> i64 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0)
+}
+
+/// A file discovered during traversal, not yet hashed.
+struct Candidate {
+    path: PathBuf,
+    length: u64,
+    mtime: i64,
+}
+
+/// Walk every search directory, skipping ignored subtrees, anything under
+/// `min_size`, and anything excluded by the extension allow/deny lists,
+/// returning the discovered candidates plus any metadata errors
+/// encountered along the way.
+fn discover_candidates(
+    search_dirs: &[&str],
+    ignore_dirs: &[PathBuf],
+    min_size: u64,
+    allowed_extensions: &[String],
+    excluded_extensions: &[String],
+    reporter: &Option<ProgressReporter>,
+) -> (Vec<Candidate>, Vec<(PathBuf, io::Error)>) {
+    let mut candidates = Vec::new();
+    let mut errors = Vec::new();
+
+    for dir in search_dirs {
+        for entry in WalkDir::new(dir)
+            .into_iter()
+            .filter_entry(|e| !ignore_dirs.iter().any(|ignored| e.path().starts_with(ignored)))
+            .filter_map(|e| e.ok())
+        {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path().to_path_buf();
+            if !extension_allowed(&path, allowed_extensions, excluded_extensions) {
+                continue;
+            }
+            match fs::metadata(&path) {
+                Ok(meta) if meta.len() >= min_size => {
+                    candidates.push(Candidate {
+                        path,
+                        length: meta.len(),
+                        mtime: mtime_nanos(&meta),
+                    });
+                    if let Some(reporter) = reporter {
+                        reporter.discovered();
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => errors.push((path, e)),
+            }
+        }
+    }
+
+    (candidates, errors)
+}
+
+/// Tracks in-flight scan counters and pushes [`ProgressData`] snapshots to
+/// a channel consumer without blocking the rayon worker pool.
+struct ProgressReporter {
+    sender: Mutex<Sender<ProgressData>>,
+    files_discovered: AtomicU64,
+    files_hashed: AtomicU64,
+    bytes_hashed: AtomicU64,
+}
+
+impl ProgressReporter {
+    fn new(sender: Sender<ProgressData>) -> ProgressReporter {
+        ProgressReporter {
+            sender: Mutex::new(sender),
+            files_discovered: AtomicU64::new(0),
+            files_hashed: AtomicU64::new(0),
+            bytes_hashed: AtomicU64::new(0),
+        }
+    }
+
+    fn discovered(&self) {
+        self.files_discovered.fetch_add(1, Ordering::Relaxed);
+        self.send(Phase::Traversal);
+    }
+
+    fn hashed(&self, bytes: u64, phase: Phase) {
+        self.files_hashed.fetch_add(1, Ordering::Relaxed);
+        self.bytes_hashed.fetch_add(bytes, Ordering::Relaxed);
+        self.send(phase);
+    }
+
+    fn send(&self, phase: Phase) {
+        let data = ProgressData {
+            phase,
+            files_discovered: self.files_discovered.load(Ordering::Relaxed),
+            files_hashed: self.files_hashed.load(Ordering::Relaxed),
+            bytes_hashed: self.bytes_hashed.load(Ordering::Relaxed),
+        };
+        let _ = self.sender.lock().unwrap().send(data);
+    }
+}
+
+/// A file passes if its extension is in the allow list (when non-empty)
+/// and not in the deny list.
+fn extension_allowed(path: &Path, allowed: &[String], excluded: &[String]) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+
+    if let Some(extension) = &extension {
+        if excluded.contains(extension) {
+            return false;
+        }
+    }
+
+    if allowed.is_empty() {
+        return true;
+    }
+    match &extension {
+        Some(extension) => allowed.contains(extension),
+        None => false,
+    }
+}
+
+fn group_by<T, K, F>(items: Vec<T>, key: F) -> Vec<(K, Vec<T>)>
+where
+    K: std::hash::Hash + Eq,
+    F: Fn(&T) -> K,
+{
+    let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+    for item in items {
+        groups.entry(key(&item)).or_default().push(item);
+    }
+    groups.into_iter().collect()
+}
+
+/// Hashes the first `len` bytes of `path`. Returns the hash alongside the
+/// number of bytes actually read, which is less than `len` for files
+/// shorter than the prefix size.
+fn hash_prefix(path: &Path, len: u64) -> io::Result<(u128, u64)> {
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0u8; len as usize];
+    let n = file.read(&mut buf)?;
+    Ok((hash_bytes(&buf[..n]), n as u64))
+}
+
+/// Hashes the full contents of `path`. Returns the hash alongside the total
+/// number of bytes read.
+fn hash_file(path: &Path) -> io::Result<(u128, u64)> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher_hi = std::collections::hash_map::DefaultHasher::new();
+    let mut hasher_lo = std::collections::hash_map::DefaultHasher::new();
+    hasher_lo.write_u8(0xA5);
+    let mut buf = [0u8; 8192];
+    let mut total = 0u64;
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher_hi.write(&buf[..n]);
+        hasher_lo.write(&buf[..n]);
+        total += n as u64;
+    }
+    Ok((
+        ((hasher_hi.finish() as u128) << 64) | hasher_lo.finish() as u128,
+        total,
+    ))
+}
+
+fn hash_bytes(data: &[u8]) -> u128 {
+    let mut hasher_hi = std::collections::hash_map::DefaultHasher::new();
+    let mut hasher_lo = std::collections::hash_map::DefaultHasher::new();
+    hasher_lo.write_u8(0xA5);
+    hasher_hi.write(data);
+    hasher_lo.write(data);
+    ((hasher_hi.finish() as u128) << 64) | hasher_lo.finish() as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "ddh_libtest_{}_{}",
+            name,
+            std::process::id()
+        ));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn entry_for(path: &Path) -> (u64, i64) {
+        let meta = fs::metadata(path).unwrap();
+        (meta.len(), mtime_nanos(&meta))
+    }
+
+    #[test]
+    fn verify_by_bytes_splits_a_group_whose_members_only_collided_on_hash() {
+        let a = temp_file("verify_bytes_a", b"same length, different content A");
+        let b = temp_file("verify_bytes_b", b"same length, different content B");
+
+        // Simulate a full-hash collision: both paths land in one Fileinfo
+        // group even though their contents differ.
+        let mut group = Fileinfo::new(32, None, None, a.clone());
+        group.add_path(b.clone());
+
+        let (verified, errors) = verify_by_bytes(vec![group]);
+
+        assert!(errors.is_empty());
+        assert_eq!(verified.len(), 2, "colliding-hash group must split apart");
+        assert!(verified.iter().all(|info| info.get_paths().len() == 1));
+
+        fs::remove_file(&a).ok();
+        fs::remove_file(&b).ok();
+    }
+
+    #[test]
+    fn verify_by_bytes_keeps_identical_files_grouped() {
+        let contents = b"identical contents";
+        let a = temp_file("verify_bytes_same_a", contents);
+        let b = temp_file("verify_bytes_same_b", contents);
+
+        let mut group = Fileinfo::new(contents.len() as u64, None, None, a.clone());
+        group.add_path(b.clone());
+
+        let (verified, errors) = verify_by_bytes(vec![group]);
+
+        assert!(errors.is_empty());
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].get_paths().len(), 2);
+
+        fs::remove_file(&a).ok();
+        fs::remove_file(&b).ok();
+    }
+
+    #[test]
+    fn files_equal_detects_differing_contents_of_the_same_length() {
+        let a = temp_file("files_equal_a", b"aaaaaaaaaa");
+        let b = temp_file("files_equal_b", b"bbbbbbbbbb");
+
+        assert!(!files_equal(&a, &b).unwrap());
+
+        fs::remove_file(&a).ok();
+        fs::remove_file(&b).ok();
+    }
+
+    #[test]
+    fn cached_or_compute_reuses_hash_on_size_and_mtime_match() {
+        let path = temp_file("cache_hit", b"hello world");
+        let (length, mtime) = entry_for(&path);
+        let cache = Mutex::new(Cache::new());
+
+        let (first, first_bytes_read) =
+            cached_or_compute(&cache, &path, length, mtime, CacheField::Full).unwrap();
+        assert_eq!(first_bytes_read, Some(length));
+        // Poison the cached hash so a cache hit must return this value rather
+        // than recomputing it from the (unchanged) file contents.
+        cache.lock().unwrap().get_mut(&path).unwrap().full_hash = Some(first.wrapping_add(1));
+
+        let (second, second_bytes_read) =
+            cached_or_compute(&cache, &path, length, mtime, CacheField::Full).unwrap();
+        assert_eq!(second, first.wrapping_add(1));
+        assert_eq!(second_bytes_read, None);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn cached_or_compute_recomputes_when_size_or_mtime_differ() {
+        let path = temp_file("cache_invalidate", b"hello world");
+        let (length, mtime) = entry_for(&path);
+        let cache = Mutex::new(Cache::new());
+
+        let (first, _) = cached_or_compute(&cache, &path, length, mtime, CacheField::Full).unwrap();
+
+        fs::write(&path, b"different contents, different hash").unwrap();
+        let (new_length, new_mtime) = entry_for(&path);
+
+        let (second, bytes_read) =
+            cached_or_compute(&cache, &path, new_length, new_mtime, CacheField::Full).unwrap();
+        assert_ne!(second, first);
+        assert_eq!(bytes_read, Some(new_length));
+
+        let stored = cache.lock().unwrap().get(&path).cloned().unwrap();
+        assert_eq!(stored.size, new_length);
+        assert_eq!(stored.full_hash, Some(second));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn cached_or_compute_treats_same_second_mtime_change_as_a_miss() {
+        let path = temp_file("cache_same_second", b"hello world");
+        let (length, mtime) = entry_for(&path);
+        let cache = Mutex::new(Cache::new());
+        cache.lock().unwrap().insert(
+            path.clone(),
+            CacheEntry {
+                size: length,
+                mtime,
+                partial_hash: None,
+                full_hash: Some(0xDEAD),
+            },
+        );
+
+        // A nanosecond-resolution mtime bump that a whole-seconds cache key
+        // would miss must still be treated as a cache invalidation.
+        let (hash, bytes_read) =
+            cached_or_compute(&cache, &path, length, mtime + 1, CacheField::Full).unwrap();
+        assert_ne!(hash, 0xDEAD);
+        assert_eq!(bytes_read, Some(length));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn cached_or_compute_partial_reports_only_the_prefix_bytes_read() {
+        let contents = vec![0u8; (PARTIAL_HASH_SIZE * 2) as usize];
+        let path = temp_file("cache_partial_bytes", &contents);
+        let (length, mtime) = entry_for(&path);
+        let cache = Mutex::new(Cache::new());
+
+        let (_, bytes_read) =
+            cached_or_compute(&cache, &path, length, mtime, CacheField::Partial).unwrap();
+        assert_eq!(bytes_read, Some(PARTIAL_HASH_SIZE));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn extension_allowed_with_empty_lists_allows_everything() {
+        assert!(extension_allowed(Path::new("a.txt"), &[], &[]));
+        assert!(extension_allowed(Path::new("a"), &[], &[]));
+    }
+
+    #[test]
+    fn extension_allowed_excluded_extension_is_rejected() {
+        let excluded = vec!["tmp".to_string()];
+        assert!(!extension_allowed(Path::new("a.tmp"), &[], &excluded));
+        assert!(extension_allowed(Path::new("a.txt"), &[], &excluded));
+    }
+
+    #[test]
+    fn extension_allowed_allow_list_rejects_everything_else() {
+        let allowed = vec!["txt".to_string()];
+        assert!(extension_allowed(Path::new("a.txt"), &allowed, &[]));
+        assert!(!extension_allowed(Path::new("a.md"), &allowed, &[]));
+        assert!(!extension_allowed(Path::new("a"), &allowed, &[]));
+    }
+
+    #[test]
+    fn extension_allowed_is_case_insensitive() {
+        let allowed = vec!["txt".to_string()];
+        assert!(extension_allowed(Path::new("A.TXT"), &allowed, &[]));
+    }
+
+    #[test]
+    fn extension_allowed_exclude_wins_over_allow() {
+        let allowed = vec!["txt".to_string()];
+        let excluded = vec!["txt".to_string()];
+        assert!(!extension_allowed(Path::new("a.txt"), &allowed, &excluded));
+    }
+}
@@ -1,10 +1,14 @@
 use clap::{App, Arg};
 use ddh::fileinfo::Fileinfo;
+use ddh::progress::ProgressData;
 use rayon::prelude::*;
 use std::fs::{self};
+use std::io;
 use std::io::prelude::*;
 use std::io::stdin;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Copy, Clone)]
 pub enum PrintFmt {
@@ -18,6 +22,25 @@ pub enum Verbosity {
     All,
 }
 
+/// What to do with the non-canonical paths in a duplicate set.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Action {
+    None,
+    Delete,
+    Hardlink,
+    Symlink,
+}
+
+/// Which path in a duplicate set is kept untouched (the "canonical" copy
+/// that others are deleted or linked against).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum KeepPolicy {
+    First,
+    Newest,
+    Oldest,
+    ShortestPath,
+}
+
 static DDH_ABOUT: &str = "Compare and contrast directories.\nExample invocation: ddh -d /home/jon/downloads /home/jon/documents -v duplicates\nExample pipe: ddh -d ~/Downloads/ -o no -v all -f json | someJsonParser.bin";
 
 fn main() {
@@ -77,6 +100,56 @@ fn main() {
                                 .default_value("0")
                                 .validator(|s| s.parse::<u64>())
                                 .help("Minimum file size in bytes to consider."))
+                        .arg(Arg::new("Action")
+                                .long("action")
+                                .possible_values(&["none", "delete", "hardlink", "symlink"])
+                                .ignore_case(true)
+                                .takes_value(true)
+                                .max_values(1)
+                                .default_value("none")
+                                .help("Resolve duplicate sets by deleting, hardlinking, or symlinking every non-canonical copy. Requires --method full or bytes."))
+                        .arg(Arg::new("Keep")
+                                .long("keep")
+                                .possible_values(&["first", "newest", "oldest", "shortest-path"])
+                                .ignore_case(true)
+                                .takes_value(true)
+                                .max_values(1)
+                                .default_value("first")
+                                .help("Which copy in a duplicate set to keep as the canonical path."))
+                        .arg(Arg::new("DryRun")
+                                .long("dry-run")
+                                .takes_value(false)
+                                .help("Print the actions an --action would take without touching the filesystem."))
+                        .arg(Arg::new("Cache")
+                                .long("cache")
+                                .value_name("Cache")
+                                .takes_value(true)
+                                .max_values(1)
+                                .help("Path to a hash cache file, reused and updated across runs to skip rehashing unchanged files."))
+                        .arg(Arg::new("Method")
+                                .long("method")
+                                .possible_values(&["size", "partial", "full", "bytes"])
+                                .ignore_case(true)
+                                .takes_value(true)
+                                .max_values(1)
+                                .default_value("full")
+                                .help("How thoroughly to compare files: size-only, first-block hash, full-file hash, or full hash plus a byte-exact verification pass. --action requires full or bytes."))
+                        .arg(Arg::new("AllowedExtensions")
+                                .long("allowed-extensions")
+                                .value_name("AllowedExtensions")
+                                .takes_value(true)
+                                .max_values(1)
+                                .help("Comma-separated list of extensions to consider; anything else is skipped."))
+                        .arg(Arg::new("ExcludedExtensions")
+                                .long("excluded-extensions")
+                                .value_name("ExcludedExtensions")
+                                .takes_value(true)
+                                .max_values(1)
+                                .help("Comma-separated list of extensions to skip."))
+                        .arg(Arg::new("Progress")
+                                .long("progress")
+                                .takes_value(false)
+                                .help("Print a throttled scan-progress line to stderr."))
                         .get_matches();
 
     let search_dirs: Vec<_> = match arguments.values_of("directories") {
@@ -92,11 +165,98 @@ fn main() {
         None => 0,
     };
 
-    let (complete_files, read_errors): (Vec<Fileinfo>, Vec<(_, _)>) =
-        ddh::deduplicate_dirs(search_dirs, ignore_dirs, min_size);
+    let cache_file = arguments.value_of("Cache").map(PathBuf::from);
+    let method = match arguments.value_of("Method").unwrap_or("full") {
+        "size" => ddh::ComparisonMethod::Size,
+        "partial" => ddh::ComparisonMethod::Partial,
+        "bytes" => ddh::ComparisonMethod::Bytes,
+        _ => ddh::ComparisonMethod::Full,
+    };
+    let allowed_extensions: Vec<_> = match arguments.value_of("AllowedExtensions") {
+        Some(s) => s.split(',').collect(),
+        None => vec![],
+    };
+    let excluded_extensions: Vec<_> = match arguments.value_of("ExcludedExtensions") {
+        Some(s) => s.split(',').collect(),
+        None => vec![],
+    };
+    let action = match arguments.value_of("Action").unwrap_or("none") {
+        "delete" => Action::Delete,
+        "hardlink" => Action::Hardlink,
+        "symlink" => Action::Symlink,
+        _ => Action::None,
+    };
+    if action != Action::None
+        && !matches!(
+            method,
+            ddh::ComparisonMethod::Full | ddh::ComparisonMethod::Bytes
+        )
+    {
+        eprintln!(
+            "--action requires --method full or bytes: size and partial only compare file length or a first-block hash, which is not enough evidence to delete or link files on."
+        );
+        std::process::exit(1);
+    }
+
+    let progress_handle = if arguments.is_present("Progress") {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handle = std::thread::spawn(move || report_progress(rx));
+        (Some(tx), Some(handle))
+    } else {
+        (None, None)
+    };
+    let (progress_tx, progress_thread) = progress_handle;
+
+    let (mut complete_files, mut read_errors): (Vec<Fileinfo>, Vec<(_, _)>) =
+        ddh::deduplicate_dirs(
+            search_dirs,
+            ddh::ScanOptions {
+                ignore_dirs,
+                min_size,
+                cache_file: cache_file.as_deref(),
+                method,
+                allowed_extensions,
+                excluded_extensions,
+                progress: progress_tx,
+            },
+        );
+    if let Some(handle) = progress_thread {
+        let _ = handle.join();
+    }
+
+    if action != Action::None {
+        let keep = match arguments.value_of("Keep").unwrap_or("first") {
+            "newest" => KeepPolicy::Newest,
+            "oldest" => KeepPolicy::Oldest,
+            "shortest-path" => KeepPolicy::ShortestPath,
+            _ => KeepPolicy::First,
+        };
+        let dry_run = arguments.is_present("DryRun");
+        let mut shared_files: Vec<&mut Fileinfo> = complete_files
+            .iter_mut()
+            .filter(|x| x.get_paths().len() > 1)
+            .collect();
+        let (bytes_reclaimed, mut action_errors) =
+            resolve_duplicates(&mut shared_files, action, keep, dry_run);
+        read_errors.append(&mut action_errors);
+        eprintln!(
+            "{} {} {} bytes reclaimed",
+            if dry_run { "Would reclaim" } else { "Reclaimed" },
+            bytes_reclaimed,
+            if action == Action::Delete {
+                "(deleted)"
+            } else {
+                "(replaced with links)"
+            }
+        );
+    }
+
+    // Re-partition after any action above, since deleting a path can shrink
+    // a duplicate set down to a single remaining path.
     let (shared_files, unique_files): (Vec<&Fileinfo>, Vec<&Fileinfo>) = complete_files
         .par_iter()
         .partition(|&x| x.get_paths().len() > 1);
+
     process_full_output(
         &shared_files,
         &unique_files,
@@ -106,6 +266,391 @@ fn main() {
     );
 }
 
+const PROGRESS_THROTTLE: Duration = Duration::from_millis(150);
+
+/// Render a throttled scan-progress line to stderr as [`ProgressData`]
+/// arrives, so stdout/JSON piping stays clean. Runs on its own thread until
+/// the sending side of the channel is dropped.
+fn report_progress(rx: Receiver<ProgressData>) {
+    let mut last_printed = Instant::now() - PROGRESS_THROTTLE;
+    while let Ok(data) = rx.recv() {
+        if last_printed.elapsed() < PROGRESS_THROTTLE {
+            continue;
+        }
+        eprint!(
+            "\r{:?}: {} discovered, {} hashed, {} bytes hashed",
+            data.phase, data.files_discovered, data.files_hashed, data.bytes_hashed
+        );
+        let _ = io::stderr().flush();
+        last_printed = Instant::now();
+    }
+    eprintln!();
+}
+
+/// Render `path` for display, canonicalized when possible. Falls back to
+/// the path as given when canonicalization fails, e.g. because the file was
+/// removed after the scan.
+fn display_path(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Pick the path to keep untouched out of a duplicate set, per `policy`.
+fn pick_canonical(paths: &[PathBuf], policy: KeepPolicy) -> PathBuf {
+    match policy {
+        KeepPolicy::First => paths[0].clone(),
+        KeepPolicy::ShortestPath => paths
+            .iter()
+            .min_by_key(|p| p.as_os_str().len())
+            .unwrap()
+            .clone(),
+        KeepPolicy::Newest | KeepPolicy::Oldest => {
+            let mut dated: Vec<(PathBuf, std::time::SystemTime)> = paths
+                .iter()
+                .map(|p| {
+                    let modified = fs::metadata(p)
+                        .and_then(|m| m.modified())
+                        .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+                    (p.clone(), modified)
+                })
+                .collect();
+            dated.sort_by_key(|(_, modified)| *modified);
+            if policy == KeepPolicy::Oldest {
+                dated.remove(0).0
+            } else {
+                dated.pop().unwrap().0
+            }
+        }
+    }
+}
+
+/// Resolve every duplicate set in `shared_files` down to a single canonical
+/// path, either deleting the rest or replacing them with hard/symlinks to
+/// the canonical copy. Returns the total bytes reclaimed (the file length
+/// once for each non-canonical path that was deleted or replaced, or in a
+/// dry run would be) and any per-file `io::Error`s hit along the way. When `dry_run` is set, nothing on disk is touched; the planned
+/// operations are printed instead.
+///
+/// On success, deleted paths are dropped from each [`Fileinfo`] so that
+/// later reporting doesn't still treat them as present on disk.
+fn resolve_duplicates(
+    shared_files: &mut [&mut Fileinfo],
+    action: Action,
+    keep: KeepPolicy,
+    dry_run: bool,
+) -> (u64, Vec<(PathBuf, io::Error)>) {
+    let mut bytes_reclaimed: u64 = 0;
+    let mut errors = Vec::new();
+
+    for file in shared_files.iter_mut() {
+        let canonical = pick_canonical(file.get_paths(), keep);
+        let candidates: Vec<PathBuf> = file
+            .get_paths()
+            .iter()
+            .filter(|p| **p != canonical)
+            .cloned()
+            .collect();
+        let mut deleted = Vec::new();
+
+        for path in candidates {
+            if dry_run {
+                eprintln!(
+                    "[dry-run] {:?} {} -> keep {:?}",
+                    action,
+                    path.display(),
+                    canonical
+                );
+                bytes_reclaimed += file.get_length();
+                continue;
+            }
+
+            let result = match action {
+                Action::None => Ok(()),
+                Action::Delete => fs::remove_file(&path),
+                Action::Hardlink | Action::Symlink => replace_with_link(&canonical, &path, action),
+            };
+            match result {
+                Ok(()) => {
+                    bytes_reclaimed += file.get_length();
+                    if action == Action::Delete {
+                        deleted.push(path);
+                    }
+                }
+                Err(e) => errors.push((path, e)),
+            }
+        }
+
+        if !deleted.is_empty() {
+            file.get_mut_paths().retain(|p| !deleted.contains(p));
+        }
+    }
+
+    (bytes_reclaimed, errors)
+}
+
+/// Replace `path` with a hard/symlink to `canonical`, without ever leaving
+/// `path` missing partway through: the link is created at a temporary path
+/// in the same directory and then renamed over `path`, so a failure (a
+/// cross-device link, a full disk, the process getting killed) leaves the
+/// original file untouched instead of deleted-but-not-replaced.
+fn replace_with_link(canonical: &Path, path: &Path, action: Action) -> io::Result<()> {
+    let tmp_name = format!(
+        ".{}.ddh-tmp-{}",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        std::process::id()
+    );
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let link_result = match action {
+        Action::Hardlink => fs::hard_link(canonical, &tmp_path),
+        Action::Symlink => {
+            // A relative target would resolve against the link's own
+            // directory, so always link to an absolute path.
+            let target = std::env::current_dir()?.join(canonical);
+            #[cfg(unix)]
+            {
+                std::os::unix::fs::symlink(&target, &tmp_path)
+            }
+            #[cfg(not(unix))]
+            {
+                std::os::windows::fs::symlink_file(&target, &tmp_path)
+            }
+        }
+        Action::None | Action::Delete => unreachable!("only called for Hardlink/Symlink"),
+    };
+    link_result?;
+
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "ddh_test_{}_{}_{}",
+            name,
+            std::process::id(),
+            name.len()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_file(path: &PathBuf, contents: &[u8]) {
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn pick_canonical_first_keeps_first_path() {
+        let dir = temp_dir("pick_first");
+        let a = dir.join("a.txt");
+        let b = dir.join("b.txt");
+        write_file(&a, b"same");
+        write_file(&b, b"same");
+
+        assert_eq!(pick_canonical(&[a.clone(), b], KeepPolicy::First), a);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn pick_canonical_shortest_path_prefers_shorter() {
+        let dir = temp_dir("pick_shortest");
+        let short = dir.join("a.txt");
+        let long = dir.join("aa.txt");
+        write_file(&short, b"same");
+        write_file(&long, b"same");
+
+        assert_eq!(
+            pick_canonical(&[long, short.clone()], KeepPolicy::ShortestPath),
+            short
+        );
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn resolve_duplicates_delete_keeps_canonical_and_removes_rest() {
+        let dir = temp_dir("resolve_delete");
+        let a = dir.join("a.txt");
+        let b = dir.join("b.txt");
+        let contents = b"duplicate content";
+        write_file(&a, contents);
+        write_file(&b, contents);
+
+        let mut info = Fileinfo::new(contents.len() as u64, None, None, a.clone());
+        info.add_path(b.clone());
+        let mut shared_files = vec![&mut info];
+
+        let (bytes_reclaimed, errors) =
+            resolve_duplicates(&mut shared_files, Action::Delete, KeepPolicy::First, false);
+
+        assert!(errors.is_empty());
+        assert_eq!(bytes_reclaimed, contents.len() as u64);
+        assert!(a.exists());
+        assert!(!b.exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn resolve_duplicates_delete_prunes_removed_path_from_fileinfo() {
+        let dir = temp_dir("resolve_delete_prune");
+        let a = dir.join("a.txt");
+        let b = dir.join("b.txt");
+        let contents = b"duplicate content";
+        write_file(&a, contents);
+        write_file(&b, contents);
+
+        let mut info = Fileinfo::new(contents.len() as u64, None, None, a.clone());
+        info.add_path(b.clone());
+        let mut shared_files = vec![&mut info];
+
+        resolve_duplicates(&mut shared_files, Action::Delete, KeepPolicy::First, false);
+
+        assert_eq!(info.get_paths(), &vec![a]);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn resolve_duplicates_dry_run_does_not_touch_filesystem() {
+        let dir = temp_dir("resolve_dry_run");
+        let a = dir.join("a.txt");
+        let b = dir.join("b.txt");
+        let contents = b"duplicate content";
+        write_file(&a, contents);
+        write_file(&b, contents);
+
+        let mut info = Fileinfo::new(contents.len() as u64, None, None, a.clone());
+        info.add_path(b.clone());
+        let mut shared_files = vec![&mut info];
+
+        let (bytes_reclaimed, errors) =
+            resolve_duplicates(&mut shared_files, Action::Delete, KeepPolicy::First, true);
+
+        assert!(errors.is_empty());
+        assert_eq!(bytes_reclaimed, contents.len() as u64);
+        assert!(a.exists());
+        assert!(b.exists());
+        assert_eq!(info.get_paths(), &vec![a, b]);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn resolve_duplicates_hardlink_keeps_original_path_present_on_success() {
+        let dir = temp_dir("resolve_hardlink");
+        let a = dir.join("a.txt");
+        let b = dir.join("b.txt");
+        let contents = b"duplicate content";
+        write_file(&a, contents);
+        write_file(&b, contents);
+
+        let mut info = Fileinfo::new(contents.len() as u64, None, None, a.clone());
+        info.add_path(b.clone());
+        let mut shared_files = vec![&mut info];
+
+        let (_, errors) =
+            resolve_duplicates(&mut shared_files, Action::Hardlink, KeepPolicy::First, false);
+
+        assert!(errors.is_empty());
+        assert!(a.exists());
+        assert!(b.exists());
+        // The replaced path is unchanged from the caller's point of view: it
+        // is still a valid path for this Fileinfo, just now a link.
+        assert_eq!(info.get_paths(), &vec![a, b]);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn replace_with_link_leaves_original_in_place_when_link_target_is_gone() {
+        let dir = temp_dir("replace_with_link_failure");
+        let canonical = dir.join("missing.txt");
+        let path = dir.join("a.txt");
+        write_file(&path, b"still here");
+
+        // `canonical` does not exist, so the hard_link syscall must fail
+        // before `path` is ever touched.
+        let result = replace_with_link(&canonical, &path, Action::Hardlink);
+
+        assert!(result.is_err());
+        assert!(path.exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn space_summary_reports_zero_reclaimable_with_no_duplicates() {
+        let files = vec![
+            Fileinfo::new(10, None, None, PathBuf::from("a.txt")),
+            Fileinfo::new(20, None, None, PathBuf::from("b.txt")),
+        ];
+
+        let summary = SpaceSummary::new(&files);
+
+        assert_eq!(summary.reclaimable_bytes, 0);
+        assert_eq!(summary.dedup_ratio, 1.0);
+    }
+
+    #[test]
+    fn space_summary_counts_extra_paths_in_a_duplicate_set_as_reclaimable() {
+        let mut duplicate = Fileinfo::new(10, None, None, PathBuf::from("a.txt"));
+        duplicate.add_path(PathBuf::from("b.txt"));
+        duplicate.add_path(PathBuf::from("c.txt"));
+        let files = vec![duplicate, Fileinfo::new(5, None, None, PathBuf::from("d.txt"))];
+
+        let summary = SpaceSummary::new(&files);
+
+        // 3 copies of a 10-byte file plus one unique 5-byte file: 35 bytes
+        // with duplicates, 15 without, so 20 bytes are reclaimable.
+        assert_eq!(summary.reclaimable_bytes, 20);
+        assert!((summary.dedup_ratio - 35.0 / 15.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn space_summary_on_empty_input_has_no_reclaimable_space() {
+        let summary = SpaceSummary::new(&[]);
+
+        assert_eq!(summary.reclaimable_bytes, 0);
+        assert_eq!(summary.dedup_ratio, 1.0);
+    }
+}
+
+/// How much space duplicates are wasting, and how much smaller the tree
+/// would be without them.
+#[derive(Debug, Copy, Clone, serde::Serialize)]
+pub struct SpaceSummary {
+    reclaimable_bytes: u64,
+    dedup_ratio: f64,
+}
+
+impl SpaceSummary {
+    fn new(complete_files: &[Fileinfo]) -> SpaceSummary {
+        let with_duplicates: u64 = complete_files
+            .par_iter()
+            .map(|x| (x.get_paths().len() as u64) * x.get_length())
+            .sum();
+        let without_duplicates: u64 = complete_files.par_iter().map(|x| x.get_length()).sum();
+        let dedup_ratio = if without_duplicates > 0 {
+            with_duplicates as f64 / without_duplicates as f64
+        } else {
+            1.0
+        };
+        SpaceSummary {
+            reclaimable_bytes: with_duplicates - without_duplicates,
+            dedup_ratio,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct JsonOutput<T: serde::Serialize> {
+    summary: SpaceSummary,
+    files: T,
+}
+
 fn process_full_output(
     shared_files: &[&Fileinfo],
     unique_files: &[&Fileinfo],
@@ -180,6 +725,14 @@ fn process_full_output(
             .sum::<u64>()
     );
 
+    let summary = SpaceSummary::new(complete_files);
+    println!(
+        "Reclaimable space: {} {} ({:.2}x dedup ratio)",
+        summary.reclaimable_bytes / display_divisor,
+        blocksize,
+        summary.dedup_ratio
+    );
+
     match (fmt, verbosity) {
         (_, Verbosity::Quiet) => {}
         (PrintFmt::Standard, Verbosity::Duplicates) => {
@@ -192,23 +745,13 @@ fn process_full_output(
                 );
                 x.get_paths()
                     .par_iter()
-                    .for_each(|y| println!("\t{}", y.canonicalize().unwrap().to_str().unwrap()));
+                    .for_each(|y| println!("\t{}", display_path(y)));
             })
         }
         (PrintFmt::Standard, Verbosity::All) => {
             println!("Single instance files");
             unique_files.par_iter().for_each(|x| {
-                println!(
-                    "{}",
-                    x.get_paths()
-                        .iter()
-                        .next()
-                        .unwrap()
-                        .canonicalize()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                )
+                println!("{}", display_path(x.get_paths().first().unwrap()))
             });
             println!("Shared instance files and instance locations");
             shared_files.iter().for_each(|x| {
@@ -219,7 +762,7 @@ fn process_full_output(
                 );
                 x.get_paths()
                     .par_iter()
-                    .for_each(|y| println!("\t{}", y.canonicalize().unwrap().to_str().unwrap()));
+                    .for_each(|y| println!("\t{}", display_path(y)));
             });
             error_paths.iter().for_each(|x| {
                 println!(
@@ -230,15 +773,23 @@ fn process_full_output(
             })
         }
         (PrintFmt::Json, Verbosity::Duplicates) => {
+            let output = JsonOutput {
+                summary,
+                files: shared_files,
+            };
             println!(
                 "{}",
-                serde_json::to_string(shared_files).unwrap_or_else(|_| "".to_string())
+                serde_json::to_string(&output).unwrap_or_else(|_| "".to_string())
             );
         }
         (PrintFmt::Json, Verbosity::All) => {
+            let output = JsonOutput {
+                summary,
+                files: complete_files,
+            };
             println!(
                 "{}",
-                serde_json::to_string(complete_files).unwrap_or_else(|_| "".to_string())
+                serde_json::to_string(&output).unwrap_or_else(|_| "".to_string())
             );
         }
     }
@@ -288,6 +839,7 @@ fn process_full_output(
                 shared_files,
                 unique_files,
                 complete_files,
+                summary,
                 destination_string,
             );
         }
@@ -299,6 +851,7 @@ fn write_results_to_file(
     shared_files: &[&Fileinfo],
     unique_files: &[&Fileinfo],
     complete_files: &[Fileinfo],
+    summary: SpaceSummary,
     file: &str,
 ) {
     let mut output = fs::File::create(file).expect("Error opening output file for writing");
@@ -326,10 +879,14 @@ fn write_results_to_file(
             }
         }
         PrintFmt::Json => {
+            let output_data = JsonOutput {
+                summary,
+                files: complete_files,
+            };
             output
                 .write_fmt(format_args!(
                     "{}",
-                    serde_json::to_string(complete_files)
+                    serde_json::to_string(&output_data)
                         .unwrap_or_else(|_| "Error deserializing".to_string())
                 ))
                 .unwrap();
@@ -0,0 +1,17 @@
+/// Which stage of a scan a [`ProgressData`] update was emitted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Traversal,
+    PartialHash,
+    FullHash,
+}
+
+/// A snapshot of scan progress, sent over a channel so a consumer (e.g. a
+/// stderr progress line) can render it without blocking the worker pool.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressData {
+    pub phase: Phase,
+    pub files_discovered: u64,
+    pub files_hashed: u64,
+    pub bytes_hashed: u64,
+}